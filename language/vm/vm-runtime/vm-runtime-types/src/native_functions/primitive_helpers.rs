@@ -1,5 +1,6 @@
 use super::dispatch::NativeReturnStatus;
-use crate::value::Value;
+use crate::native_functions::context::NativeContext;
+use crate::{loaded_data::types::Type, value::Reference, value::Value};
 use libra_types::{
     account_address::AccountAddress,
     byte_array::ByteArray,
@@ -7,23 +8,135 @@ use libra_types::{
 };
 use std::collections::VecDeque;
 
-pub fn native_bytearray_concat(mut arguments: VecDeque<Value>) -> NativeReturnStatus {
+/// Sub-status returned when `BCS::from_bytes<T>` is asked to parse bytes that
+/// do not match the requested type layout. This is a normal, catchable Move
+/// abort rather than a VM invariant violation.
+const E_TYPE_NOT_MATCH: u64 = 1;
+
+/// Sub-status returned when `BCS::to_bytes<T>` is asked to serialize a type
+/// argument whose layout cannot be resolved, or a value that layout cannot
+/// describe. Like `E_TYPE_NOT_MATCH`, the caller fully controls `T`, so this
+/// is a catchable abort rather than a VM invariant violation.
+const E_BCS_SERIALIZATION_FAILURE: u64 = 2;
+
+/// Gas parameters for natives whose cost is a fixed base charge plus a
+/// per-byte charge (per output byte, except `native_from_bytes`, which
+/// charges per input byte). Each native below takes its own instance so the
+/// chain's gas schedule can tune them independently.
+///
+/// Note on the "wrong number of arguments" branches below: they return
+/// `NativeReturnStatus::InvariantError`, which only wraps a `VMStatus` and has
+/// no field to carry a cost. That's fine because a mismatched argument count
+/// is not a gas-metered outcome in the first place — the bytecode verifier
+/// guarantees every call site supplies the arity a native's declared Move
+/// signature expects, so reaching that branch means the verifier itself has a
+/// bug, not that a well-formed transaction exercised an unpriced path.
+///
+/// That reasoning does not extend to anything derived from a caller-supplied
+/// type argument. `native_to_bytes` and `native_from_bytes` both resolve a
+/// runtime layout for a generic `T` the calling transaction chose, so a
+/// layout that fails to resolve is reachable input, not a verifier bug — both
+/// natives charge gas on that path (via `NativeReturnStatus::Aborted`):
+/// `native_to_bytes` charges `gas_params.base`, and `native_from_bytes`
+/// charges `gas_params.base` plus `gas_params.per_byte` per input byte, the
+/// same cost as its bytes-mismatch path.
+pub struct ByteArrayConcatGasParameters {
+    pub base: u64,
+    pub per_byte: u64,
+}
+
+pub struct ByteArrayPushByteGasParameters {
+    pub base: u64,
+    pub per_byte: u64,
+}
+
+pub struct AddressToBytesGasParameters {
+    pub base: u64,
+    pub per_byte: u64,
+}
+
+pub struct U64ToBytesGasParameters {
+    pub base: u64,
+    pub per_byte: u64,
+}
+
+pub struct U128ToBytesGasParameters {
+    pub base: u64,
+    pub per_byte: u64,
+}
+
+pub struct U8ToBytesGasParameters {
+    pub base: u64,
+    pub per_byte: u64,
+}
+
+pub struct ToBytesGasParameters {
+    pub base: u64,
+    pub per_byte: u64,
+}
+
+pub struct FromBytesGasParameters {
+    pub base: u64,
+    pub per_byte: u64,
+}
+
+/// Folds a `vector<vector<u8>>` into a single `ByteArray` in one native call,
+/// so Move code assembling a message out of many pieces (e.g. before hashing
+/// or signing) doesn't need a chain of pairwise `concat` calls each
+/// allocating a fresh buffer. An empty outer vector yields an empty
+/// `ByteArray`.
+pub fn native_bytearray_concat(
+    mut arguments: VecDeque<Value>,
+    gas_params: &ByteArrayConcatGasParameters,
+) -> NativeReturnStatus {
+    if arguments.len() != 1 {
+        let msg = format!(
+            "wrong number of arguments for bytearray_concat expected 1 found {}",
+            arguments.len()
+        );
+        return NativeReturnStatus::InvariantError(
+            VMStatus::new(StatusCode::UNREACHABLE).with_message(msg),
+        );
+    }
+    let byte_arrays = pop_arg!(arguments, Vec<ByteArray>);
+
+    let total_len: usize = byte_arrays.iter().map(|b| b.as_bytes().len()).sum();
+    let mut return_val = Vec::with_capacity(total_len);
+    for byte_array in &byte_arrays {
+        return_val.extend_from_slice(byte_array.as_bytes());
+    }
+
+    let cost = gas_params.base + gas_params.per_byte * (return_val.len() as u64);
+    let return_values = vec![Value::byte_array(ByteArray::new(return_val))];
+    NativeReturnStatus::Success {
+        cost,
+        return_values,
+    }
+}
+
+/// The `byte` builder complement to `native_bytearray_concat`: appends a
+/// single `u8` to the end of a `ByteArray`, so Move code can assemble a
+/// `ByteArray` one byte at a time (mirroring `Vector::push_back` for the raw
+/// byte-array type) instead of only by concatenating whole `ByteArray`s.
+pub fn native_bytearray_push_byte(
+    mut arguments: VecDeque<Value>,
+    gas_params: &ByteArrayPushByteGasParameters,
+) -> NativeReturnStatus {
     if arguments.len() != 2 {
         let msg = format!(
-            "wrong number of arguments for bytearray_concat expected 2 found {}",
+            "wrong number of arguments for bytearray_push_byte expected 2 found {}",
             arguments.len()
         );
         return NativeReturnStatus::InvariantError(
             VMStatus::new(StatusCode::UNREACHABLE).with_message(msg),
         );
     }
-    let arg2 = pop_arg!(arguments, ByteArray);
-    let arg1 = pop_arg!(arguments, ByteArray);
-    let mut return_val = arg1.as_bytes().to_vec();
-    return_val.extend_from_slice(arg2.as_bytes());
+    let byte = pop_arg!(arguments, u8);
+    let bytearray = pop_arg!(arguments, ByteArray);
+    let mut return_val = bytearray.as_bytes().to_vec();
+    return_val.push(byte);
 
-    // TODO: Figure out the gas cost for concatenation.
-    let cost = return_val.len() as u64;
+    let cost = gas_params.base + gas_params.per_byte * (return_val.len() as u64);
     let return_values = vec![Value::byte_array(ByteArray::new(return_val))];
     NativeReturnStatus::Success {
         cost,
@@ -31,7 +144,10 @@ pub fn native_bytearray_concat(mut arguments: VecDeque<Value>) -> NativeReturnSt
     }
 }
 
-pub fn native_address_to_bytes(mut arguments: VecDeque<Value>) -> NativeReturnStatus {
+pub fn native_address_to_bytes(
+    mut arguments: VecDeque<Value>,
+    gas_params: &AddressToBytesGasParameters,
+) -> NativeReturnStatus {
     if arguments.len() != 1 {
         let msg = format!(
             "wrong number of arguments for address_to_bytes expected 1 found {}",
@@ -44,8 +160,7 @@ pub fn native_address_to_bytes(mut arguments: VecDeque<Value>) -> NativeReturnSt
     let arg = pop_arg!(arguments, AccountAddress);
     let return_val = arg.to_vec();
 
-    // TODO: Figure out the gas cost for conversion.
-    let cost = return_val.len() as u64;
+    let cost = gas_params.base + gas_params.per_byte * (return_val.len() as u64);
     let return_values = vec![Value::byte_array(ByteArray::new(return_val))];
     NativeReturnStatus::Success {
         cost,
@@ -53,7 +168,12 @@ pub fn native_address_to_bytes(mut arguments: VecDeque<Value>) -> NativeReturnSt
     }
 }
 
-pub fn native_u64_to_bytes(mut arguments: VecDeque<Value>) -> NativeReturnStatus {
+/// Little-endian `u64` to bytes, kept around for backward compatibility with
+/// existing callers. Always emits 8 bytes.
+pub fn native_u64_to_bytes(
+    mut arguments: VecDeque<Value>,
+    gas_params: &U64ToBytesGasParameters,
+) -> NativeReturnStatus {
     if arguments.len() != 1 {
         let msg = format!(
             "wrong number of arguments for u64_to_bytes expected 1 found {}",
@@ -66,11 +186,333 @@ pub fn native_u64_to_bytes(mut arguments: VecDeque<Value>) -> NativeReturnStatus
     let arg = pop_arg!(arguments, u64);
     let return_val: Vec<u8> = arg.to_le_bytes().to_vec();
 
-    // TODO: Figure out the gas cost for conversion.
-    let cost = return_val.len() as u64;
+    let cost = gas_params.base + gas_params.per_byte * (return_val.len() as u64);
+    let return_values = vec![Value::byte_array(ByteArray::new(return_val))];
+    NativeReturnStatus::Success {
+        cost,
+        return_values,
+    }
+}
+
+/// Big-endian (network byte order) `u64` to bytes, for interoperating with
+/// external systems and cross-chain protocols that expect that ordering
+/// instead of the little-endian output of [`native_u64_to_bytes`]. Always
+/// emits 8 bytes.
+pub fn native_u64_to_bytes_be(
+    mut arguments: VecDeque<Value>,
+    gas_params: &U64ToBytesGasParameters,
+) -> NativeReturnStatus {
+    if arguments.len() != 1 {
+        let msg = format!(
+            "wrong number of arguments for u64_to_bytes_be expected 1 found {}",
+            arguments.len()
+        );
+        return NativeReturnStatus::InvariantError(
+            VMStatus::new(StatusCode::UNREACHABLE).with_message(msg),
+        );
+    }
+    let arg = pop_arg!(arguments, u64);
+    let return_val: Vec<u8> = arg.to_be_bytes().to_vec();
+
+    let cost = gas_params.base + gas_params.per_byte * (return_val.len() as u64);
+    let return_values = vec![Value::byte_array(ByteArray::new(return_val))];
+    NativeReturnStatus::Success {
+        cost,
+        return_values,
+    }
+}
+
+/// Little-endian `u128` to bytes. Always emits 16 bytes.
+pub fn native_u128_to_bytes(
+    mut arguments: VecDeque<Value>,
+    gas_params: &U128ToBytesGasParameters,
+) -> NativeReturnStatus {
+    if arguments.len() != 1 {
+        let msg = format!(
+            "wrong number of arguments for u128_to_bytes expected 1 found {}",
+            arguments.len()
+        );
+        return NativeReturnStatus::InvariantError(
+            VMStatus::new(StatusCode::UNREACHABLE).with_message(msg),
+        );
+    }
+    let arg = pop_arg!(arguments, u128);
+    let return_val: Vec<u8> = arg.to_le_bytes().to_vec();
+
+    let cost = gas_params.base + gas_params.per_byte * (return_val.len() as u64);
+    let return_values = vec![Value::byte_array(ByteArray::new(return_val))];
+    NativeReturnStatus::Success {
+        cost,
+        return_values,
+    }
+}
+
+/// Big-endian `u128` to bytes. Always emits 16 bytes.
+pub fn native_u128_to_bytes_be(
+    mut arguments: VecDeque<Value>,
+    gas_params: &U128ToBytesGasParameters,
+) -> NativeReturnStatus {
+    if arguments.len() != 1 {
+        let msg = format!(
+            "wrong number of arguments for u128_to_bytes_be expected 1 found {}",
+            arguments.len()
+        );
+        return NativeReturnStatus::InvariantError(
+            VMStatus::new(StatusCode::UNREACHABLE).with_message(msg),
+        );
+    }
+    let arg = pop_arg!(arguments, u128);
+    let return_val: Vec<u8> = arg.to_be_bytes().to_vec();
+
+    let cost = gas_params.base + gas_params.per_byte * (return_val.len() as u64);
     let return_values = vec![Value::byte_array(ByteArray::new(return_val))];
     NativeReturnStatus::Success {
         cost,
         return_values,
     }
 }
+
+/// `u8` to bytes. Endianness is not meaningful for a single byte; this exists
+/// so callers interoperating with fixed-width wire formats don't need to
+/// special-case the narrowest integer width. Always emits 1 byte.
+pub fn native_u8_to_bytes(
+    mut arguments: VecDeque<Value>,
+    gas_params: &U8ToBytesGasParameters,
+) -> NativeReturnStatus {
+    if arguments.len() != 1 {
+        let msg = format!(
+            "wrong number of arguments for u8_to_bytes expected 1 found {}",
+            arguments.len()
+        );
+        return NativeReturnStatus::InvariantError(
+            VMStatus::new(StatusCode::UNREACHABLE).with_message(msg),
+        );
+    }
+    let arg = pop_arg!(arguments, u8);
+    let return_val: Vec<u8> = vec![arg];
+
+    let cost = gas_params.base + gas_params.per_byte * (return_val.len() as u64);
+    let return_values = vec![Value::byte_array(ByteArray::new(return_val))];
+    NativeReturnStatus::Success {
+        cost,
+        return_values,
+    }
+}
+
+/// Generic `BCS::to_bytes<T>(&T): vector<u8>` native. Unlike the hand-written
+/// conversions above, this works for any Move value (structs, vectors, nested
+/// types) by resolving the runtime type layout for the type argument and
+/// running it through BCS serialization.
+pub fn native_to_bytes(
+    context: &mut dyn NativeContext,
+    mut ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+    gas_params: &ToBytesGasParameters,
+) -> NativeReturnStatus {
+    if ty_args.len() != 1 || arguments.len() != 1 {
+        let msg = format!(
+            "wrong number of type arguments/arguments for to_bytes expected 1/1 found {}/{}",
+            ty_args.len(),
+            arguments.len()
+        );
+        return NativeReturnStatus::InvariantError(
+            VMStatus::new(StatusCode::UNREACHABLE).with_message(msg),
+        );
+    }
+    let ty = ty_args.pop().expect("ty_args must have exactly one element");
+
+    // `ty` is the caller-supplied type argument, not something the bytecode
+    // verifier constrains, so a layout that fails to resolve is reachable by
+    // a well-formed (if adversarial) transaction. That makes it a catchable
+    // abort, not a VM invariant violation.
+    let layout = match context.type_to_type_layout(&ty) {
+        Ok(Some(layout)) => layout,
+        Ok(None) | Err(_) => {
+            return NativeReturnStatus::Aborted {
+                cost: gas_params.base,
+                error_code: E_BCS_SERIALIZATION_FAILURE,
+            };
+        }
+    };
+
+    let reference = pop_arg!(arguments, Reference);
+    let return_val = match reference.read_ref().simple_serialize(&layout) {
+        Some(bytes) => bytes,
+        None => {
+            return NativeReturnStatus::Aborted {
+                cost: gas_params.base,
+                error_code: E_BCS_SERIALIZATION_FAILURE,
+            };
+        }
+    };
+
+    let cost = gas_params.base + gas_params.per_byte * (return_val.len() as u64);
+    let return_values = vec![Value::byte_array(ByteArray::new(return_val))];
+    NativeReturnStatus::Success {
+        cost,
+        return_values,
+    }
+}
+
+/// Inverse of [`native_to_bytes`]: `BCS::from_bytes<T>(bytes: vector<u8>): T`.
+/// Resolves the target type layout and deserializes the given bytes into a
+/// Move value. Malformed input does not indicate a VM bug, so a layout
+/// mismatch aborts the transaction with `E_TYPE_NOT_MATCH` instead of raising
+/// an invariant violation.
+pub fn native_from_bytes(
+    context: &mut dyn NativeContext,
+    mut ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+    gas_params: &FromBytesGasParameters,
+) -> NativeReturnStatus {
+    if ty_args.len() != 1 || arguments.len() != 1 {
+        let msg = format!(
+            "wrong number of type arguments/arguments for from_bytes expected 1/1 found {}/{}",
+            ty_args.len(),
+            arguments.len()
+        );
+        return NativeReturnStatus::InvariantError(
+            VMStatus::new(StatusCode::UNREACHABLE).with_message(msg),
+        );
+    }
+    let ty = ty_args.pop().expect("ty_args must have exactly one element");
+    let bytes = pop_arg!(arguments, ByteArray);
+
+    // The base charge is paid regardless of outcome, including below when the
+    // layout itself fails to resolve: a well-formed transaction fully
+    // controls `T`, so an unresolvable layout is reachable caller input, not
+    // a VM bug, and must not go unmetered any more than a failed
+    // deserialization does.
+    let cost = gas_params.base + gas_params.per_byte * (bytes.as_bytes().len() as u64);
+
+    let layout = match context.type_to_type_layout(&ty) {
+        Ok(Some(layout)) => layout,
+        Ok(None) | Err(_) => {
+            return NativeReturnStatus::Aborted {
+                cost,
+                error_code: E_TYPE_NOT_MATCH,
+            };
+        }
+    };
+
+    match Value::simple_deserialize(bytes.as_bytes(), &layout) {
+        Some(value) => NativeReturnStatus::Success {
+            cost,
+            return_values: vec![value],
+        },
+        None => NativeReturnStatus::Aborted {
+            cost,
+            error_code: E_TYPE_NOT_MATCH,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loaded_data::types::FatType;
+
+    struct MockContext {
+        layout: Result<Option<FatType>, VMStatus>,
+    }
+
+    impl NativeContext for MockContext {
+        fn type_to_type_layout(&self, _ty: &Type) -> Result<Option<FatType>, VMStatus> {
+            self.layout.clone()
+        }
+    }
+
+    fn to_bytes_gas() -> ToBytesGasParameters {
+        ToBytesGasParameters {
+            base: 10,
+            per_byte: 1,
+        }
+    }
+
+    fn from_bytes_gas() -> FromBytesGasParameters {
+        FromBytesGasParameters {
+            base: 10,
+            per_byte: 1,
+        }
+    }
+
+    #[test]
+    fn to_bytes_aborts_instead_of_invariant_error_when_layout_unresolved() {
+        let gas_params = to_bytes_gas();
+        let mut context = MockContext { layout: Ok(None) };
+        let ty_args = vec![Type::U64];
+        let arguments = VecDeque::from(vec![Value::byte_array(ByteArray::new(vec![]))]);
+
+        match native_to_bytes(&mut context, ty_args, arguments, &gas_params) {
+            NativeReturnStatus::Aborted { cost, error_code } => {
+                assert_eq!(cost, gas_params.base);
+                assert_eq!(error_code, E_BCS_SERIALIZATION_FAILURE);
+            }
+            _ => panic!("unresolved layout should abort, not raise an invariant error"),
+        }
+    }
+
+    #[test]
+    fn from_bytes_aborts_instead_of_invariant_error_when_layout_unresolved() {
+        let gas_params = from_bytes_gas();
+        let mut context = MockContext {
+            layout: Err(VMStatus::new(StatusCode::VALUE_DESERIALIZATION_ERROR)),
+        };
+        let ty_args = vec![Type::U64];
+        let bytes = vec![1, 2, 3];
+        let arguments = VecDeque::from(vec![Value::byte_array(ByteArray::new(bytes.clone()))]);
+
+        match native_from_bytes(&mut context, ty_args, arguments, &gas_params) {
+            NativeReturnStatus::Aborted { cost, error_code } => {
+                assert_eq!(cost, gas_params.base + gas_params.per_byte * bytes.len() as u64);
+                assert_eq!(error_code, E_TYPE_NOT_MATCH);
+            }
+            _ => panic!("unresolved layout should abort, not raise an invariant error"),
+        }
+    }
+
+    #[test]
+    fn from_bytes_aborts_on_malformed_input_for_a_resolved_layout() {
+        let gas_params = from_bytes_gas();
+        let mut context = MockContext {
+            layout: Ok(Some(FatType::U64)),
+        };
+        let ty_args = vec![Type::U64];
+        // A BCS-encoded u64 is exactly 8 bytes; 3 bytes cannot deserialize.
+        let bytes = vec![1, 2, 3];
+        let arguments = VecDeque::from(vec![Value::byte_array(ByteArray::new(bytes.clone()))]);
+
+        match native_from_bytes(&mut context, ty_args, arguments, &gas_params) {
+            NativeReturnStatus::Aborted { cost, error_code } => {
+                assert_eq!(cost, gas_params.base + gas_params.per_byte * bytes.len() as u64);
+                assert_eq!(error_code, E_TYPE_NOT_MATCH);
+            }
+            _ => panic!("malformed input should abort with E_TYPE_NOT_MATCH"),
+        }
+    }
+
+    #[test]
+    fn from_bytes_round_trip_succeeds_for_a_well_formed_u64() {
+        let gas_params = from_bytes_gas();
+        let value = Value::u64(42);
+        let bytes = value
+            .simple_serialize(&FatType::U64)
+            .expect("a u64 always serializes");
+        let mut context = MockContext {
+            layout: Ok(Some(FatType::U64)),
+        };
+        let ty_args = vec![Type::U64];
+        let arguments = VecDeque::from(vec![Value::byte_array(ByteArray::new(bytes.clone()))]);
+
+        match native_from_bytes(&mut context, ty_args, arguments, &gas_params) {
+            NativeReturnStatus::Success {
+                cost,
+                return_values,
+            } => {
+                assert_eq!(cost, gas_params.base + gas_params.per_byte * bytes.len() as u64);
+                assert_eq!(return_values.len(), 1);
+            }
+            _ => panic!("a well-formed u64 round trip should succeed"),
+        }
+    }
+}